@@ -3,7 +3,8 @@ use aide::{
     generate::GenContext,
     openapi::{
         CookieStyle::Form, HeaderStyle::Simple, MediaType, Operation, Parameter, ParameterData,
-        ParameterSchemaOrContent::Schema, Response as OpenApiResponse, SchemaObject,
+        ParameterSchemaOrContent::Schema, PathStyle, QueryStyle, Response as OpenApiResponse,
+        SchemaObject,
     },
     transform::TransformOperation,
     OperationOutput,
@@ -14,6 +15,7 @@ use axum::{
     response::{IntoResponse, Response as AxumResponse},
 };
 use paste::paste;
+use schemars::schema::{InstanceType, Schema, SchemaObject as JsonSchemaObject};
 
 /// Wraps an API router to add tags to all its routes
 pub struct TagApiRouter<S> {
@@ -130,6 +132,52 @@ pub fn simple_parameter_data(
     }
 }
 
+/// Returns a path parameter to be used with [aide::operation::add_parameters].
+///
+/// Unlike [simple_parameter_data], this doesn't need a [GenContext]: path parameters extracted
+/// from a route string are always plain strings, so the schema can be built without registering
+/// anything in the schema generator.
+pub fn simple_path_parameter(name: String, description: String) -> Parameter {
+    Parameter::Path {
+        parameter_data: bare_string_parameter_data(name, description, true),
+        style: PathStyle::Simple,
+    }
+}
+
+/// Returns a query parameter to be used with [aide::operation::add_parameters].
+///
+/// See [simple_path_parameter] for why this doesn't need a [GenContext].
+pub fn simple_query_parameter(name: String, description: String, required: bool) -> Parameter {
+    Parameter::Query {
+        parameter_data: bare_string_parameter_data(name, description, required),
+        style: QueryStyle::Form,
+        allow_reserved: false,
+        allow_empty_value: None,
+    }
+}
+
+/// Returns parameter data for a plain string, without needing a [GenContext].
+fn bare_string_parameter_data(name: String, description: String, required: bool) -> ParameterData {
+    ParameterData {
+        name,
+        required,
+        description: Some(description),
+        deprecated: Default::default(),
+        format: Schema(SchemaObject {
+            json_schema: Schema::Object(JsonSchemaObject {
+                instance_type: Some(InstanceType::String.into()),
+                ..Default::default()
+            }),
+            example: Default::default(),
+            external_docs: Default::default(),
+        }),
+        example: Default::default(),
+        examples: Default::default(),
+        explode: Default::default(),
+        extensions: Default::default(),
+    }
+}
+
 /// When a route uses the proc macro `#[aide_docs]`, calling this macro with the router's name
 /// expands to an ApiMethodRouter to be used in [aide::axum::ApiRouter::api_route].
 #[macro_export]
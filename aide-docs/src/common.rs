@@ -0,0 +1,50 @@
+use std::str::FromStr;
+use syn::{Attribute, Expr, ExprLit, Lit, Meta, MetaNameValue};
+
+/// Takes a string and converts it into a [proc_macro2::TokenStream].
+///
+/// # Panics
+/// Will panic if the input string cannot be parsed into a valid TokenStream. The function is only
+/// run at compile time, so panicking is fine.
+pub(crate) fn tokens_from_string(string: String) -> proc_macro2::TokenStream {
+    proc_macro2::TokenStream::from_str(&string).unwrap()
+}
+
+/// Takes a slice of [Attribute]s, finds those that are non-empty doc comments, and returns them as
+/// [String]s.
+pub(crate) fn collect_doc_comments(attrs: &[Attribute]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for attr in attrs {
+        // Skip attributes that aren't doc comments
+        if !attr.path().is_ident("doc") {
+            continue;
+        }
+
+        // Unpack literal string from doc comment
+        if let Meta::NameValue(MetaNameValue {
+            value:
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit_str),
+                    ..
+                }),
+            ..
+        }) = &attr.meta
+        {
+            // If the doc comment isn't empty, trim it and add it to `lines`
+            if !lit_str.value().is_empty() {
+                lines.push(lit_str.value().trim().into())
+            }
+        }
+    }
+
+    lines
+}
+
+/// Splits a slice of strings into a summary and description. The first string in the slice
+/// becomes the summary, and the remaining strings are joined with newlines to become the description.
+pub(crate) fn split_summary_description(lines: &[String]) -> (String, String) {
+    match lines.split_first() {
+        Some((summary, desc)) => (summary.clone(), desc.join("\n")),
+        None => ("".into(), "".into()),
+    }
+}
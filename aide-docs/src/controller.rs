@@ -0,0 +1,129 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    meta::{parser, ParseNestedMeta},
+    parse_macro_input, Attribute, Ident, ImplItem, ItemImpl, LitStr, Token,
+};
+
+use crate::common::{collect_doc_comments, split_summary_description};
+
+/// Expands `#[aide_controller]` on an `impl` block into the original block plus a generated
+/// `fn router() -> ApiRouter<S>` wiring up every `#[route(...)]`-annotated method.
+pub(crate) fn expand(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(item as ItemImpl);
+
+    let mut attrs = AideControllerAttributes::default();
+    let aide_controller_parser = parser(|meta| attrs.parse(meta));
+    parse_macro_input!(args with aide_controller_parser);
+
+    let self_ty = input.self_ty.clone();
+    let mut routes = Vec::new();
+
+    for impl_item in &mut input.items {
+        let ImplItem::Fn(method) = impl_item else {
+            continue;
+        };
+
+        let Some(route_index) = method.attrs.iter().position(|attr| attr.path().is_ident("route"))
+        else {
+            continue;
+        };
+        let route_attr = method.attrs.remove(route_index);
+
+        let route = match RouteSpec::parse(&route_attr) {
+            Ok(route) => route,
+            Err(err) => return err.to_compile_error().into(),
+        };
+
+        let doc_lines = collect_doc_comments(&method.attrs);
+        let (summary, description) = split_summary_description(&doc_lines);
+
+        let handler = &method.sig.ident;
+        // The bare `get`/`post`/... routers only take a handler; the `_with` variants (the same
+        // ones `with_aide_docs!` builds) also take the docs closure.
+        let http_method = format_ident!("{}_with", route.method);
+        let path = route.path;
+
+        routes.push(quote! {
+            .api_route(#path, aide::axum::routing::#http_method(
+                Self::#handler,
+                |op| op.summary(#summary).description(#description),
+            ))
+        });
+    }
+
+    let router = match attrs.tag {
+        Some(tag) => quote! {
+            aide_axum_utils::TagApiRouter::new(#tag)
+                #(#routes)*
+                .into()
+        },
+        None => quote! {
+            aide::axum::ApiRouter::new()
+                #(#routes)*
+        },
+    };
+
+    let expanded = quote! {
+        #input
+
+        impl #self_ty {
+            /// Generated by `#[aide_controller]`: wires every `#[route(...)]`-annotated method up
+            /// to an [aide::axum::ApiRouter].
+            ///
+            /// `S` is left generic so the router can be `.nest()`ed into an app-wide
+            /// `ApiRouter<S>` built elsewhere. That only type-checks if no `#[route]` handler pins
+            /// itself to a concrete state via `axum::extract::State<SomeConcreteState>` — doing so
+            /// fixes that handler's router to `ApiMethodRouter<SomeConcreteState>`, which won't
+            /// unify with the generic `S` this function returns. Controllers with stateful
+            /// handlers aren't supported; such a handler should take `State<S>` generically
+            /// instead, or the controller should be written by hand.
+            pub fn router<S: Clone + Send + Sync + 'static>() -> aide::axum::ApiRouter<S> {
+                #router
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+#[derive(Default)]
+struct AideControllerAttributes {
+    tag: Option<String>,
+}
+
+impl AideControllerAttributes {
+    /// Parses macro arguments into [AideControllerAttributes]
+    fn parse(&mut self, meta: ParseNestedMeta) -> syn::parse::Result<()> {
+        if meta.path.is_ident("tag") {
+            self.tag = Some(meta.value()?.parse::<LitStr>()?.value());
+            Ok(())
+        } else {
+            let ident = meta
+                .path
+                .get_ident()
+                .map(|i| i.to_string())
+                .unwrap_or_default();
+
+            Err(meta.error(format!("unsupported property '{ident}'",)))
+        }
+    }
+}
+
+/// A single `#[route(method, "path")]` attribute on a controller method.
+struct RouteSpec {
+    method: Ident,
+    path: LitStr,
+}
+
+impl RouteSpec {
+    /// Parses `#[route(get, "/:id")]` into a [RouteSpec].
+    fn parse(attr: &Attribute) -> syn::Result<Self> {
+        attr.parse_args_with(|input: syn::parse::ParseStream| {
+            let method: Ident = input.parse()?;
+            input.parse::<Token![,]>()?;
+            let path: LitStr = input.parse()?;
+            Ok(RouteSpec { method, path })
+        })
+    }
+}
@@ -0,0 +1,35 @@
+use syn::ItemFn;
+
+/// Wraps `item`'s body in a `tracing` span named `span_name`, carrying the route's `tag` and
+/// `path` as fields (empty when not given).
+///
+/// `async fn` bodies are run under [`tracing::Instrument::instrument`] rather than a held
+/// `.enter()` guard: an `Entered` guard kept alive across the body's `.await` points would make
+/// the handler's future `!Send`, breaking axum's `Handler` bound, and leaving a span "entered"
+/// while its task is suspended is the documented `tracing` anti-pattern. Sync bodies have no
+/// `.await` points to worry about, so they keep the cheaper `.enter()` guard.
+///
+/// Gated behind this crate's `tracing` feature: with the feature disabled this is a no-op, so
+/// crates that never opt into `#[aide_docs(instrument)]` don't pay for a `tracing` dependency.
+#[cfg(feature = "tracing")]
+pub(crate) fn wrap(item: &mut ItemFn, span_name: &str, tag: Option<&str>, path: Option<&str>) {
+    let tag = tag.unwrap_or_default();
+    let path = path.unwrap_or_default();
+    let block = &item.block;
+
+    item.block = if item.sig.asyncness.is_some() {
+        syn::parse_quote! {{
+            let __aide_docs_span = ::tracing::info_span!(#span_name, tag = #tag, path = #path);
+            ::tracing::Instrument::instrument(async move #block, __aide_docs_span).await
+        }}
+    } else {
+        syn::parse_quote! {{
+            let __aide_docs_span = ::tracing::info_span!(#span_name, tag = #tag, path = #path);
+            let _guard = __aide_docs_span.enter();
+            #block
+        }}
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn wrap(_item: &mut ItemFn, _span_name: &str, _tag: Option<&str>, _path: Option<&str>) {}
@@ -1,16 +1,31 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use std::str::FromStr;
 use syn::{
     meta::{parser, ParseNestedMeta},
-    parse_macro_input, Attribute, Expr, ExprLit, ItemFn, Lit, LitStr, Meta, MetaNameValue,
+    parse_macro_input, punctuated::Punctuated, FnArg, GenericArgument, ItemFn, LitInt, LitStr,
+    PatType, PathArguments, PathSegment, Token, Type,
 };
 
+mod common;
+mod controller;
+mod instrument;
+mod sections;
+
+use common::{collect_doc_comments, tokens_from_string};
+use sections::parse_doc_sections;
+
 /// A procedural macro that generates a function to add API documentation to a route.
 ///
 /// This macro processes doc comments above the function and extracts:
 /// - A summary (first line of the doc comment)
-/// - A description (subsequent lines of doc comments)
+/// - A description (subsequent lines of doc comments, except those under the headings below)
+///
+/// It also recognizes a few Markdown headings and routes them into their own OpenAPI fields
+/// instead of the flat description:
+/// - `# Errors` - `- STATUS: description` bullets become documented error responses, the same as
+///   a `response(...)` attribute.
+/// - `# Security` - bullet lines name the security schemes required by the route.
+/// - `# Examples` - fenced code blocks are collected and appended to the description.
 ///
 /// **Optional parameters**
 ///
@@ -18,12 +33,46 @@ use syn::{
 ///   same router, consider using [TagApiRouter] instead.
 ///
 /// - `deprecated` - Mark the route as deprecated: `#[aide_docs(deprecated)]`
+///
+/// - `response` - Document an additional response for the route, repeatable:
+///   `#[aide_docs(response(status = 404, description = "Todo not found"))]`. A `type` can be
+///   given to document the response body, e.g. `response(status = 200, type = Json<Todo>)`; it
+///   defaults to `()` for bodyless responses.
+///
+/// - `path` - The route's path, e.g. `#[aide_docs(path = "/todos/:id")]`. Required when the
+///   handler takes an `axum::extract::Path<T>`: the named segments (`:id`/`{id}`) are derived
+///   into path parameter docs, and checked against `T`'s field count at doc-generation time.
+///
+/// An `axum::extract::Query<T>` argument needs no extra attribute: its parameters are derived
+/// straight from `T`'s [schemars::JsonSchema] properties.
+///
+/// - `instrument` - Wraps the handler body in a `tracing` span named after the function (or a
+///   given `name`), tagged with the route's tag: `#[aide_docs(instrument)]` or
+///   `#[aide_docs(instrument(name = "get_todo"))]`. Requires this crate's `tracing` feature; it's
+///   a no-op otherwise, so non-users pay nothing.
 #[proc_macro_attribute]
 pub fn aide_docs(args: TokenStream, item: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(item as ItemFn);
+    let mut input = parse_macro_input!(item as ItemFn);
 
     let doc_lines = collect_doc_comments(&input.attrs);
-    let (summary, description) = split_summary_description(&doc_lines);
+    let doc_sections = parse_doc_sections(&doc_lines);
+    let summary = doc_sections.summary;
+    // Ideally these would become `example` values on the generated response's `MediaType`, but
+    // we don't know which response/media type they belong to at this point, so fold them into
+    // the description instead.
+    let description = match doc_sections.examples.is_empty() {
+        true => doc_sections.description,
+        false => format!(
+            "{}\n\nExamples:\n\n{}",
+            doc_sections.description,
+            doc_sections
+                .examples
+                .iter()
+                .map(|example| format!("```\n{example}\n```"))
+                .collect::<Vec<_>>()
+                .join("\n\n")
+        ),
+    };
 
     let mut attrs = AideDocsAttributes::default();
 
@@ -33,9 +82,21 @@ pub fn aide_docs(args: TokenStream, item: TokenStream) -> TokenStream {
     // Parse the macro invocation's arguments, adding them to the `attrs` struct.
     parse_macro_input!(args with aide_docs_parser);
 
+    // `# Errors` bullets from the doc comment are documented the same way as explicit
+    // `response(...)` attributes.
+    attrs
+        .responses
+        .extend(doc_sections.errors.into_iter().map(|(status, description)| {
+            ResponseSpec {
+                status,
+                description: Some(description),
+                ty: None,
+            }
+        }));
+
     // If a tag was provided, create a snippet of code which adds the tag to the route.
     // If not, create an empty bit of code.
-    let tag_snippet = match attrs.tag {
+    let tag_snippet = match &attrs.tag {
         Some(tag) => tokens_from_string(format!(r#".tag("{}")"#, tag)),
         None => proc_macro2::TokenStream::default(),
     };
@@ -46,8 +107,35 @@ pub fn aide_docs(args: TokenStream, item: TokenStream) -> TokenStream {
         false => proc_macro2::TokenStream::default(),
     };
 
+    let response_snippet = response_snippets(&attrs.responses);
+    let security_snippet = security_snippet(&doc_sections.security);
+
+    let extractors = Extractors::from_inputs(&input.sig.inputs);
+    let parameter_snippet = match parameter_snippets(&extractors, &attrs) {
+        Ok(snippet) => snippet,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
     let aide_docs_fn = tokens_from_string(format!("__aide_docs_{}", input.sig.ident));
 
+    if let Some(name) = &attrs.instrument {
+        let span_name = name.clone().unwrap_or_else(|| input.sig.ident.to_string());
+        instrument::wrap(
+            &mut input,
+            &span_name,
+            attrs.tag.as_deref(),
+            attrs.path.as_deref(),
+        );
+    }
+
+    // Only bind `op` as `mut` when a snippet below actually mutates it in place; otherwise the
+    // binding is never reassigned after the initial builder chain and `mut` would trigger an
+    // `unused_mut` warning in every downstream crate using the plain `#[aide_docs]`/`tag`-only form.
+    let mut_kw = (!deprecated_snippet.is_empty()
+        || !parameter_snippet.is_empty()
+        || !security_snippet.is_empty())
+    .then(|| quote!(mut));
+
     let expanded = quote! {
         #input
 
@@ -55,8 +143,10 @@ pub fn aide_docs(args: TokenStream, item: TokenStream) -> TokenStream {
         ) -> impl FnOnce(aide::transform::TransformOperation<'_>) -> aide::transform::TransformOperation<'_>
         {
             move |op| {
-                let mut op = op.summary(#summary).description(#description)#tag_snippet;
+                let #mut_kw op = op.summary(#summary).description(#description) #tag_snippet #response_snippet;
                 #deprecated_snippet
+                #parameter_snippet
+                #security_snippet
                 op
             }
         }
@@ -65,19 +155,233 @@ pub fn aide_docs(args: TokenStream, item: TokenStream) -> TokenStream {
     expanded.into()
 }
 
-/// Takes a string and converts it into a [proc_macro2::TokenStream].
+/// The inner `T` of the handler's `axum::extract::Path<T>` and/or `axum::extract::Query<T>`
+/// extractor, when present.
+#[derive(Default)]
+struct Extractors {
+    path: Option<Type>,
+    query: Option<Type>,
+}
+
+impl Extractors {
+    /// Scans a function's arguments for `Path<T>`/`Query<T>` extractors, capturing `T`.
+    fn from_inputs(inputs: &Punctuated<FnArg, Token![,]>) -> Self {
+        let mut extractors = Extractors::default();
+
+        for input in inputs {
+            let FnArg::Typed(PatType { ty, .. }) = input else {
+                continue;
+            };
+
+            let Type::Path(type_path) = ty.as_ref() else {
+                continue;
+            };
+
+            let Some(segment) = type_path.path.segments.last() else {
+                continue;
+            };
+
+            let Some(inner) = generic_argument(segment) else {
+                continue;
+            };
+
+            if segment.ident == "Path" {
+                extractors.path = Some(inner);
+            } else if segment.ident == "Query" {
+                extractors.query = Some(inner);
+            }
+        }
+
+        extractors
+    }
+}
+
+/// Extracts the first (and for `Path`/`Query`, only) type argument of a generic path segment
+/// like `Path<Todo>`.
+fn generic_argument(segment: &PathSegment) -> Option<Type> {
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+
+    args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    })
+}
+
+/// Builds the code registering path/query parameters derived from the handler's extractors,
+/// pushing them onto `op.inner_mut().parameters` directly (the closure only has a
+/// `TransformOperation`, not the `GenContext` that `aide::operation::add_parameters` needs).
+///
+/// A function-level attribute macro only ever sees `T`'s name where it appears in `Path<T>`/
+/// `Query<T>` — not `T`'s definition — so the field names that make up a parameter list can't be
+/// read at macro-expansion time. `T: schemars::JsonSchema` is already required for these
+/// extractors to implement aide's `OperationInput`, though, and reflecting through it *is*
+/// available at doc-generation time (when the generated closure actually runs), which is what the
+/// code below does instead: query parameters are built straight from `T`'s schema properties, and
+/// the path's named segments (read from the `path` attribute, since nothing in the signature
+/// carries the route string) are checked against `T`'s field count, panicking with a clear message
+/// on mismatch rather than failing silently.
+fn parameter_snippets(
+    extractors: &Extractors,
+    attrs: &AideDocsAttributes,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut snippet = proc_macro2::TokenStream::new();
+
+    if let Some(path_ty) = &extractors.path {
+        let path = attrs.path.as_ref().ok_or_else(|| {
+            syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "`path` is required on `aide_docs` when the handler takes a `Path<T>` extractor",
+            )
+        })?;
+
+        let names = path_parameter_names(path);
+        if names.is_empty() {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                format!("no named path segments (`:name` or `{{name}}`) found in \"{path}\""),
+            ));
+        }
+        let expected_count = names.len();
+
+        snippet.extend(quote! {
+            {
+                let __schema = schemars::schema_for!(#path_ty);
+                let __field_count = __schema
+                    .schema
+                    .object
+                    .as_ref()
+                    .map(|object| object.properties.len())
+                    .filter(|count| *count > 0)
+                    .unwrap_or(1);
+                assert_eq!(
+                    __field_count, #expected_count,
+                    "`Path<{}>` has {} field(s) but route {:?} declares {} named segment(s)",
+                    stringify!(#path_ty), __field_count, #path, #expected_count,
+                );
+            }
+        });
+
+        for name in names {
+            snippet.extend(quote! {
+                op.inner_mut().parameters.push(aide::openapi::ReferenceOr::Item(
+                    aide_axum_utils::simple_path_parameter(
+                        #name.to_string(),
+                        format!("{} path parameter", #name),
+                    ),
+                ));
+            });
+        }
+    }
+
+    if let Some(query_ty) = &extractors.query {
+        snippet.extend(quote! {
+            {
+                let __schema = schemars::schema_for!(#query_ty);
+                if let Some(object) = &__schema.schema.object {
+                    for (name, sub_schema) in &object.properties {
+                        let required = object.required.contains(name);
+                        op.inner_mut().parameters.push(aide::openapi::ReferenceOr::Item(
+                            aide::openapi::Parameter::Query {
+                                parameter_data: aide::openapi::ParameterData {
+                                    name: name.clone(),
+                                    required,
+                                    description: Some(format!("{name} query parameter")),
+                                    deprecated: Default::default(),
+                                    format: aide::openapi::ParameterSchemaOrContent::Schema(
+                                        aide::openapi::SchemaObject {
+                                            json_schema: sub_schema.clone(),
+                                            example: Default::default(),
+                                            external_docs: Default::default(),
+                                        },
+                                    ),
+                                    example: Default::default(),
+                                    examples: Default::default(),
+                                    explode: Default::default(),
+                                    extensions: Default::default(),
+                                },
+                                style: aide::openapi::QueryStyle::Form,
+                                allow_reserved: false,
+                                allow_empty_value: None,
+                            },
+                        ));
+                    }
+                }
+            }
+        });
+    }
+
+    Ok(snippet)
+}
+
+/// Builds the code registering a single security requirement that lists every scheme named in a
+/// `# Security` doc comment section, so all of them are required together.
+fn security_snippet(schemes: &[String]) -> proc_macro2::TokenStream {
+    if schemes.is_empty() {
+        return proc_macro2::TokenStream::default();
+    }
+
+    let requirement = schemes
+        .iter()
+        .map(|scheme| format!(r#"("{scheme}".to_string(), Vec::new())"#))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    tokens_from_string(format!(
+        "op.inner_mut().security.get_or_insert_with(Vec::new).push(aide::openapi::SecurityRequirement::from([{requirement}]));"
+    ))
+}
+
+/// Extracts the named segments (`:name` or `{name}`) from a route path.
+fn path_parameter_names(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter_map(|segment| {
+            if let Some(name) = segment.strip_prefix(':') {
+                Some(name.to_string())
+            } else {
+                segment
+                    .strip_prefix('{')
+                    .and_then(|s| s.strip_suffix('}'))
+                    .map(str::to_string)
+            }
+        })
+        .collect()
+}
+
+/// Builds the chained `.response(...)`/`.response_with(...)` calls for every [ResponseSpec]
+/// collected from `#[aide_docs(response(...))]` attributes.
 ///
-/// # Panics
-/// Will panic if the input string cannot be parsed into a valid TokenStream. The function is only
-/// run at compile time, so panicking is fine.
-fn tokens_from_string(string: String) -> proc_macro2::TokenStream {
-    proc_macro2::TokenStream::from_str(&string).unwrap()
+/// The description (and the `ty` path) are spliced in via `quote!` rather than `format!`ed into
+/// source text, the same way `summary`/`description` are above, so a description containing `"`
+/// or `\` doesn't produce invalid generated code.
+fn response_snippets(responses: &[ResponseSpec]) -> proc_macro2::TokenStream {
+    let mut snippet = proc_macro2::TokenStream::new();
+
+    for response in responses {
+        let status = proc_macro2::Literal::u16_unsuffixed(response.status);
+        let ty = tokens_from_string(response.ty.clone().unwrap_or_else(|| "()".to_string()));
+
+        snippet.extend(match &response.description {
+            Some(description) => quote! {
+                .response_with::<#status, #ty, _>(|res| res.description(#description))
+            },
+            None => quote! {
+                .response::<#status, #ty>()
+            },
+        });
+    }
+
+    snippet
 }
 
 #[derive(Default)]
 struct AideDocsAttributes {
     tag: Option<String>,
     deprecated: bool,
+    responses: Vec<ResponseSpec>,
+    path: Option<String>,
+    instrument: Option<Option<String>>,
 }
 
 impl AideDocsAttributes {
@@ -89,6 +393,28 @@ impl AideDocsAttributes {
         } else if meta.path.is_ident("deprecated") {
             self.deprecated = true;
             Ok(())
+        } else if meta.path.is_ident("response") {
+            self.responses.push(ResponseSpec::parse(meta)?);
+            Ok(())
+        } else if meta.path.is_ident("path") {
+            self.path = Some(meta.value()?.parse::<LitStr>()?.value());
+            Ok(())
+        } else if meta.path.is_ident("instrument") {
+            if meta.input.peek(syn::token::Paren) {
+                let mut name = None;
+                meta.parse_nested_meta(|nested| {
+                    if nested.path.is_ident("name") {
+                        name = Some(nested.value()?.parse::<LitStr>()?.value());
+                        Ok(())
+                    } else {
+                        Err(nested.error("unsupported property in `instrument`"))
+                    }
+                })?;
+                self.instrument = Some(name);
+            } else {
+                self.instrument = Some(None);
+            }
+            Ok(())
         } else {
             let ident = meta
                 .path
@@ -101,41 +427,68 @@ impl AideDocsAttributes {
     }
 }
 
-/// Takes a slice of [Attribute]s, finds those that are non-empty doc comments, and returns them as
-/// [String]s.
-fn collect_doc_comments(attrs: &[Attribute]) -> Vec<String> {
-    let mut lines = Vec::new();
-    for attr in attrs {
-        // Skip attributes that aren't doc comments
-        if !attr.path().is_ident("doc") {
-            continue;
-        }
+/// A single `response(...)` entry declared on `#[aide_docs]`.
+#[derive(Default)]
+struct ResponseSpec {
+    status: u16,
+    description: Option<String>,
+    ty: Option<String>,
+}
 
-        // Unpack literal string from doc comment
-        if let Meta::NameValue(MetaNameValue {
-            value:
-                Expr::Lit(ExprLit {
-                    lit: Lit::Str(lit_str),
-                    ..
-                }),
-            ..
-        }) = &attr.meta
-        {
-            // If the doc comment isn't empty, trim it and add it to `lines`
-            if !lit_str.value().is_empty() {
-                lines.push(lit_str.value().trim().into())
+impl ResponseSpec {
+    /// Parses a `response(status = ..., description = ..., type = ...)` nested meta into a
+    /// [ResponseSpec].
+    fn parse(meta: ParseNestedMeta) -> syn::parse::Result<Self> {
+        let mut spec = ResponseSpec::default();
+        let mut status = None;
+
+        meta.parse_nested_meta(|nested| {
+            if nested.path.is_ident("status") {
+                status = Some(nested.value()?.parse::<LitInt>()?.base10_parse::<u16>()?);
+                Ok(())
+            } else if nested.path.is_ident("description") {
+                spec.description = Some(nested.value()?.parse::<LitStr>()?.value());
+                Ok(())
+            } else if nested.path.is_ident("type") {
+                let ty: syn::Path = nested.value()?.parse()?;
+                spec.ty = Some(quote!(#ty).to_string());
+                Ok(())
+            } else {
+                let ident = nested
+                    .path
+                    .get_ident()
+                    .map(|i| i.to_string())
+                    .unwrap_or_default();
+
+                Err(nested.error(format!("unsupported property '{ident}' in `response`",)))
             }
-        }
-    }
+        })?;
 
-    lines
-}
+        spec.status = status.ok_or_else(|| meta.error("`response` requires a `status`"))?;
 
-/// Splits a slice of strings into a summary and description. The first string in the slice
-/// becomes the summary, and the remaining strings are joined with newlines to become the description.
-fn split_summary_description(lines: &[String]) -> (String, String) {
-    match lines.split_first() {
-        Some((summary, desc)) => (summary.clone(), desc.join("\n")),
-        None => ("".into(), "".into()),
+        Ok(spec)
     }
 }
+
+/// A procedural macro that decorates an `impl` block of handler methods and generates a
+/// `fn router() -> ApiRouter<S>` wiring each one up, complete with its `aide_docs`-style
+/// documentation.
+///
+/// Each method to expose as a route is annotated with `#[route(method, "path")]`, e.g.
+/// `#[route(get, "/:id")]`. Doc comments above the method are split into a summary (first line)
+/// and a description (all remaining lines). Unlike [aide_docs], headings such as `# Errors` get no
+/// special handling here: they stay in the description as plain text.
+///
+/// **Optional parameters**
+///
+/// - `tag` - Categorize every route generated for this controller:
+///   `#[aide_controller(tag = "Users")]`. Implemented via [TagApiRouter].
+///
+/// The generated `router<S>()` is generic over `S` so it can be nested into an app-wide router
+/// built elsewhere; it does not support `#[route]` handlers that extract a concrete
+/// `axum::extract::State<SomeConcreteState>`, only ones that are state-agnostic or take
+/// `State<S>` generically.
+#[proc_macro_attribute]
+pub fn aide_controller(args: TokenStream, item: TokenStream) -> TokenStream {
+    controller::expand(args, item)
+}
@@ -0,0 +1,103 @@
+//! Section-aware parsing of doc comments, mapping idiomatic Rust doc headings onto OpenAPI
+//! fields instead of flattening everything into the description.
+
+/// The distinct pieces parsed out of a doc comment. Everything before the first `# Heading`
+/// becomes the summary (first line) and description (the rest), matching the old flat behaviour.
+/// `# Errors`, `# Examples` and `# Security` headings are routed into their own fields. Any other
+/// heading line is dropped, and the lines under it are treated like those before the first heading.
+pub(crate) struct DocSections {
+    pub(crate) summary: String,
+    pub(crate) description: String,
+    /// `- STATUS: description` bullets collected from a `# Errors` section.
+    pub(crate) errors: Vec<(u16, String)>,
+    /// Bullet lines collected from a `# Security` section, naming a security scheme each.
+    pub(crate) security: Vec<String>,
+    /// Fenced code blocks collected from a `# Examples` section, one entry per block. Lines come
+    /// from `collect_doc_comments`, which already trims each line and drops blank ones, so
+    /// indentation and blank lines inside the fence are not preserved.
+    pub(crate) examples: Vec<String>,
+}
+
+#[derive(PartialEq)]
+enum Section {
+    Summary,
+    Errors,
+    Examples,
+    Security,
+}
+
+/// Parses a slice of doc comment lines (as returned by `collect_doc_comments`) into [DocSections].
+pub(crate) fn parse_doc_sections(lines: &[String]) -> DocSections {
+    let mut section = Section::Summary;
+    let mut summary = None;
+    let mut description_lines = Vec::new();
+    let mut errors = Vec::new();
+    let mut security = Vec::new();
+    let mut examples = Vec::new();
+    let mut in_fence = false;
+    let mut current_example = Vec::new();
+
+    for line in lines {
+        if let Some(heading) = line.strip_prefix("# ") {
+            if in_fence {
+                // An unterminated fence before the next heading; flush what we have.
+                examples.push(current_example.join("\n"));
+                current_example.clear();
+                in_fence = false;
+            }
+
+            section = match heading {
+                "Errors" => Section::Errors,
+                "Examples" => Section::Examples,
+                "Security" => Section::Security,
+                _ => Section::Summary,
+            };
+            continue;
+        }
+
+        match section {
+            Section::Summary if summary.is_none() => summary = Some(line.clone()),
+            Section::Summary => description_lines.push(line.clone()),
+            Section::Errors => {
+                if let Some(error) = parse_error_bullet(line) {
+                    errors.push(error);
+                }
+            }
+            Section::Security => {
+                if let Some(name) = line.strip_prefix("- ") {
+                    security.push(name.trim().to_string());
+                }
+            }
+            Section::Examples => {
+                if line.trim_start().starts_with("```") {
+                    if in_fence {
+                        examples.push(current_example.join("\n"));
+                        current_example.clear();
+                    }
+                    in_fence = !in_fence;
+                } else if in_fence {
+                    current_example.push(line.clone());
+                }
+            }
+        }
+    }
+
+    if in_fence && !current_example.is_empty() {
+        examples.push(current_example.join("\n"));
+    }
+
+    DocSections {
+        summary: summary.unwrap_or_default(),
+        description: description_lines.join("\n"),
+        errors,
+        security,
+        examples,
+    }
+}
+
+/// Parses a `- 404: Todo not found` bullet line into a status code and description.
+fn parse_error_bullet(line: &str) -> Option<(u16, String)> {
+    let rest = line.strip_prefix("- ")?;
+    let (status, description) = rest.split_once(':')?;
+    Some((status.trim().parse().ok()?, description.trim().to_string()))
+}